@@ -0,0 +1,507 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Array kernels backing the [`ScalarUDFImpl`](datafusion_expr::ScalarUDFImpl)s in
+//! [`crate::udf`].
+
+use arrow::array::{
+    new_empty_array, Array, ArrayRef, FixedSizeListArray, IntervalMonthDayNanoArray,
+    LargeListArray, LargeStringArray, ListArray, MapArray, StringArray, StringBuilder,
+    TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+    TimestampSecondArray,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::datatypes::{DataType, Field, IntervalMonthDayNanoType, TimeUnit};
+use arrow::temporal_conversions::{
+    timestamp_ms_to_datetime, timestamp_ns_to_datetime, timestamp_s_to_datetime,
+    timestamp_us_to_datetime,
+};
+use arrow::util::display::array_value_to_string;
+use chrono::{Duration, Months, NaiveDateTime};
+use datafusion_common::{exec_err, DataFusionError, Result};
+use std::sync::Arc;
+
+/// Shared implementation for `map_keys`/`map_values`: projects one side (keys or
+/// values, `column` 0 or 1) of a `MapArray`'s entries `StructArray` into a `List`,
+/// re-using the map's own offset buffer and null buffer so each output row is the
+/// list of keys/values for that map entry. Top-level map nulls become null lists,
+/// and empty maps naturally produce empty (zero-length) lists.
+///
+/// The output field is always `Field::new("item", _, true)`, matching
+/// `MapKeys`/`MapValues::return_type` exactly (not the entries struct's own
+/// `"key"`/`"value"` field, which may be non-nullable) since Arrow's `List` equality
+/// includes the inner field's name/nullability and a mismatch would make
+/// `RecordBatch::try_new` reject the column at execution time.
+fn map_extract_column(args: &[ArrayRef], column: usize) -> Result<ArrayRef> {
+    let map_array = match args[0].as_any().downcast_ref::<MapArray>() {
+        Some(map_array) => map_array,
+        None => return exec_err!("map_keys/map_values can only accept a Map array"),
+    };
+
+    let entries = map_array.entries();
+    let child = Arc::clone(entries.column(column));
+    let field = Arc::new(Field::new("item", child.data_type().clone(), true));
+    let offsets = OffsetBuffer::new(map_array.offsets().clone().into_inner());
+
+    let list = ListArray::try_new(field, offsets, child, map_array.nulls().cloned())?;
+    Ok(Arc::new(list))
+}
+
+pub fn map_keys(args: &[ArrayRef]) -> Result<ArrayRef> {
+    map_extract_column(args, 0)
+}
+
+pub fn map_values(args: &[ArrayRef]) -> Result<ArrayRef> {
+    map_extract_column(args, 1)
+}
+
+/// Recursively normalizes every `FixedSizeList` level (including nested children,
+/// e.g. `FixedSizeList<FixedSizeList<Int64, 2>, 3>`) in `array` to the equivalent
+/// variable-length `List`, leaving other array types untouched. Mirrors
+/// `coerce_fixed_size_list_to_list`'s handling of the declared `return_type` one
+/// level at a time so the two can't drift apart: each level's kernel output field
+/// is built the same way the matching `return_type` level is.
+fn coerce_fixed_size_list_array(array: &ArrayRef) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::FixedSizeList(field, size) => {
+            let fixed = array.as_any().downcast_ref::<FixedSizeListArray>().ok_or_else(|| {
+                DataFusionError::Execution("expected a FixedSizeListArray".to_string())
+            })?;
+            let values = coerce_fixed_size_list_array(fixed.values())?;
+            let new_field = Arc::new(Field::new(
+                field.name(),
+                values.data_type().clone(),
+                field.is_nullable(),
+            ));
+            let offsets =
+                OffsetBuffer::from_lengths(std::iter::repeat(*size as usize).take(fixed.len()));
+            let list = ListArray::try_new(new_field, offsets, values, fixed.nulls().cloned())?;
+            Ok(Arc::new(list))
+        }
+        DataType::List(field) => {
+            let list = array.as_any().downcast_ref::<ListArray>().ok_or_else(|| {
+                DataFusionError::Execution("expected a ListArray".to_string())
+            })?;
+            let values = coerce_fixed_size_list_array(list.values())?;
+            let new_field = Arc::new(Field::new(
+                field.name(),
+                values.data_type().clone(),
+                field.is_nullable(),
+            ));
+            let new_list = ListArray::try_new(
+                new_field,
+                list.offsets().clone(),
+                values,
+                list.nulls().cloned(),
+            )?;
+            Ok(Arc::new(new_list))
+        }
+        _ => Ok(Arc::clone(array)),
+    }
+}
+
+/// Shared implementation for `array_append`/`array_prepend`: copies each row of
+/// `list` and splices `element[i]` onto the end (or front, when `append` is false)
+/// of it.
+fn append_or_prepend(list: &ListArray, element: &ArrayRef, append: bool) -> Result<ArrayRef> {
+    let mut row_arrays: Vec<ArrayRef> = Vec::with_capacity(list.len());
+    let mut offsets = vec![0i32];
+    let mut row_valid = Vec::with_capacity(list.len());
+
+    for i in 0..list.len() {
+        let row = if list.is_null(i) {
+            new_empty_array(list.values().data_type())
+        } else {
+            list.value(i)
+        };
+        let item = element.slice(i, 1);
+        let combined = if append {
+            arrow::compute::concat(&[row.as_ref(), item.as_ref()])?
+        } else {
+            arrow::compute::concat(&[item.as_ref(), row.as_ref()])?
+        };
+        offsets.push(*offsets.last().unwrap() + combined.len() as i32);
+        row_arrays.push(combined);
+        row_valid.push(true);
+    }
+
+    let values = if row_arrays.is_empty() {
+        new_empty_array(list.values().data_type())
+    } else {
+        let refs: Vec<&dyn Array> = row_arrays.iter().map(|a| a.as_ref()).collect();
+        arrow::compute::concat(&refs)?
+    };
+
+    let field = match list.data_type() {
+        DataType::List(field) => Arc::clone(field),
+        _ => unreachable!("normalized by coerce_fixed_size_list_array"),
+    };
+    let result = ListArray::try_new(
+        field,
+        OffsetBuffer::new(offsets.into()),
+        values,
+        Some(NullBuffer::from(row_valid)),
+    )?;
+    Ok(Arc::new(result))
+}
+
+fn as_list_array(array: &ArrayRef, function_name: &str) -> Result<ListArray> {
+    array
+        .as_any()
+        .downcast_ref::<ListArray>()
+        .cloned()
+        .ok_or_else(|| {
+            DataFusionError::Execution(format!(
+                "{function_name} can only accept a List/FixedSizeList argument"
+            ))
+        })
+}
+
+pub fn array_append(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let array = coerce_fixed_size_list_array(&args[0])?;
+    let list = as_list_array(&array, "array_append")?;
+    append_or_prepend(&list, &args[1], true)
+}
+
+pub fn array_prepend(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let array = coerce_fixed_size_list_array(&args[1])?;
+    let list = as_list_array(&array, "array_prepend")?;
+    append_or_prepend(&list, &args[0], false)
+}
+
+pub fn array_concat(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let arrays: Vec<ArrayRef> = args
+        .iter()
+        .map(coerce_fixed_size_list_array)
+        .collect::<Result<_>>()?;
+    let lists: Vec<ListArray> = arrays
+        .iter()
+        .map(|a| as_list_array(a, "array_concat"))
+        .collect::<Result<_>>()?;
+
+    let len = lists[0].len();
+    let child_type = match lists[0].data_type() {
+        DataType::List(field) => field.data_type().clone(),
+        _ => unreachable!("normalized by coerce_fixed_size_list_array"),
+    };
+
+    let mut row_arrays: Vec<ArrayRef> = Vec::with_capacity(len);
+    let mut offsets = vec![0i32];
+    let mut row_valid = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let rows: Vec<ArrayRef> = lists
+            .iter()
+            .filter(|list| !list.is_null(i))
+            .map(|list| list.value(i))
+            .collect();
+
+        if rows.is_empty() {
+            row_valid.push(false);
+            offsets.push(*offsets.last().unwrap());
+            continue;
+        }
+
+        let refs: Vec<&dyn Array> = rows.iter().map(|a| a.as_ref()).collect();
+        let combined = arrow::compute::concat(&refs)?;
+        offsets.push(*offsets.last().unwrap() + combined.len() as i32);
+        row_arrays.push(combined);
+        row_valid.push(true);
+    }
+
+    let values = if row_arrays.is_empty() {
+        new_empty_array(&child_type)
+    } else {
+        let refs: Vec<&dyn Array> = row_arrays.iter().map(|a| a.as_ref()).collect();
+        arrow::compute::concat(&refs)?
+    };
+
+    let field = Arc::new(Field::new("item", child_type, true));
+    let result = ListArray::try_new(
+        field,
+        OffsetBuffer::new(offsets.into()),
+        values,
+        Some(NullBuffer::from(row_valid)),
+    )?;
+    Ok(Arc::new(result))
+}
+
+/// Builds the `List<Timestamp>` result for `range`/`generate_series` when the
+/// bounds are `Timestamp`s advanced by a `MonthDayNano` interval, e.g.
+/// `range(ts1, ts2, INTERVAL '1' MONTH)`. Supports descending ranges (a negative
+/// step iterates while `current > stop` instead of `current < stop`), returning an
+/// empty list rather than erroring when the step's sign doesn't match the
+/// start/stop ordering, and rejects a zero step.
+pub fn gen_range_timestamp(args: &[ArrayRef], include_upper: bool) -> Result<ArrayRef> {
+    let (unit, tz) = match args[0].data_type() {
+        DataType::Timestamp(unit, tz) => (*unit, tz.clone()),
+        other => return exec_err!("range/generate_series expected a Timestamp array, got {other}"),
+    };
+
+    let start = timestamp_values(args[0].as_ref(), unit)?;
+    let stop = timestamp_values(args[1].as_ref(), unit)?;
+    let steps = args[2]
+        .as_any()
+        .downcast_ref::<IntervalMonthDayNanoArray>()
+        .ok_or_else(|| {
+            DataFusionError::Execution(
+                "range/generate_series step must be an Interval(MonthDayNano) array".to_string(),
+            )
+        })?;
+
+    let mut flat: Vec<Option<i64>> = Vec::new();
+    let mut offsets = vec![0i32];
+    let mut row_valid = Vec::with_capacity(start.len());
+
+    for i in 0..start.len() {
+        let (Some(start_ts), Some(stop_ts)) = (start[i], stop[i]) else {
+            row_valid.push(false);
+            offsets.push(*offsets.last().unwrap());
+            continue;
+        };
+        if steps.is_null(i) {
+            row_valid.push(false);
+            offsets.push(*offsets.last().unwrap());
+            continue;
+        }
+
+        let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(steps.value(i));
+        if months == 0 && days == 0 && nanos == 0 {
+            return exec_err!("range/generate_series step interval cannot be zero");
+        }
+        let descending = months < 0 || (months == 0 && (days < 0 || (days == 0 && nanos < 0)));
+
+        let mut current = start_ts;
+        loop {
+            let past_stop = if descending {
+                if include_upper {
+                    current < stop_ts
+                } else {
+                    current <= stop_ts
+                }
+            } else if include_upper {
+                current > stop_ts
+            } else {
+                current >= stop_ts
+            };
+            if past_stop {
+                break;
+            }
+            flat.push(Some(current));
+            current = shift_timestamp(current, unit, months, days, nanos)?;
+        }
+
+        row_valid.push(true);
+        offsets.push(flat.len() as i32);
+    }
+
+    let values = build_timestamp_array(flat, unit, tz.clone());
+    let field = Arc::new(Field::new("item", DataType::Timestamp(unit, tz), true));
+    let list = ListArray::try_new(
+        field,
+        OffsetBuffer::new(offsets.into()),
+        values,
+        Some(NullBuffer::from(row_valid)),
+    )?;
+    Ok(Arc::new(list))
+}
+
+/// Reads a `Timestamp` array's raw values regardless of its unit.
+fn timestamp_values(array: &dyn Array, unit: TimeUnit) -> Result<Vec<Option<i64>>> {
+    macro_rules! collect {
+        ($ARR_TY:ty) => {
+            array
+                .as_any()
+                .downcast_ref::<$ARR_TY>()
+                .ok_or_else(|| {
+                    DataFusionError::Execution(
+                        "range/generate_series: mismatched Timestamp unit".to_string(),
+                    )
+                })?
+                .iter()
+                .collect()
+        };
+    }
+    Ok(match unit {
+        TimeUnit::Second => collect!(TimestampSecondArray),
+        TimeUnit::Millisecond => collect!(TimestampMillisecondArray),
+        TimeUnit::Microsecond => collect!(TimestampMicrosecondArray),
+        TimeUnit::Nanosecond => collect!(TimestampNanosecondArray),
+    })
+}
+
+/// Builds a `Timestamp` array of the given unit/timezone from raw values.
+fn build_timestamp_array(
+    values: Vec<Option<i64>>,
+    unit: TimeUnit,
+    tz: Option<Arc<str>>,
+) -> ArrayRef {
+    macro_rules! with_tz {
+        ($ARR_TY:ty) => {
+            Arc::new(<$ARR_TY>::from(values).with_timezone_opt(tz))
+        };
+    }
+    match unit {
+        TimeUnit::Second => with_tz!(TimestampSecondArray),
+        TimeUnit::Millisecond => with_tz!(TimestampMillisecondArray),
+        TimeUnit::Microsecond => with_tz!(TimestampMicrosecondArray),
+        TimeUnit::Nanosecond => with_tz!(TimestampNanosecondArray),
+    }
+}
+
+/// Advances a raw timestamp value by a `MonthDayNano` interval's components,
+/// applying month/day arithmetic on the UTC datetime before adding the
+/// nanosecond remainder. The timestamp's timezone is not taken into account: month
+/// and day steps follow the UTC calendar, not the local one.
+fn shift_timestamp(value: i64, unit: TimeUnit, months: i32, days: i32, nanos: i64) -> Result<i64> {
+    let overflow = || {
+        DataFusionError::Execution(
+            "range/generate_series step produced an out-of-range timestamp".to_string(),
+        )
+    };
+
+    let datetime: NaiveDateTime = match unit {
+        TimeUnit::Second => timestamp_s_to_datetime(value),
+        TimeUnit::Millisecond => timestamp_ms_to_datetime(value),
+        TimeUnit::Microsecond => timestamp_us_to_datetime(value),
+        TimeUnit::Nanosecond => timestamp_ns_to_datetime(value),
+    }
+    .ok_or_else(overflow)?;
+
+    let shifted = if months >= 0 {
+        datetime.checked_add_months(Months::new(months as u32))
+    } else {
+        datetime.checked_sub_months(Months::new((-months) as u32))
+    }
+    .and_then(|dt| dt.checked_add_signed(Duration::days(days as i64)))
+    .and_then(|dt| dt.checked_add_signed(Duration::nanoseconds(nanos)))
+    .ok_or_else(overflow)?;
+
+    Ok(match unit {
+        TimeUnit::Second => shifted.and_utc().timestamp(),
+        TimeUnit::Millisecond => shifted.and_utc().timestamp_millis(),
+        TimeUnit::Microsecond => shifted.and_utc().timestamp_micros(),
+        TimeUnit::Nanosecond => shifted.and_utc().timestamp_nanos_opt().ok_or_else(overflow)?,
+    })
+}
+
+/// Joins each row of a `List`/`LargeList`/`FixedSizeList` array into a delimited
+/// string, recursing into nested lists so multi-dimensional arrays flatten with the
+/// same delimiter. Matches PostgreSQL's `array_to_string(array, delimiter[,
+/// null_string])`: a `NULL` element is replaced with `null_string` when given, or
+/// skipped entirely when the third argument is absent.
+pub fn array_to_string(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let array = &args[0];
+    let delimiter = get_scalar_utf8(&args[1], "delimiter")?;
+    let null_string = args
+        .get(2)
+        .map(|arg| get_scalar_utf8(arg, "null_string"))
+        .transpose()?;
+
+    let mut builder = StringBuilder::new();
+    for i in 0..array.len() {
+        if array.is_null(i) {
+            builder.append_null();
+            continue;
+        }
+        let mut joined = String::new();
+        append_list_row(array, i, &delimiter, null_string.as_deref(), &mut joined)?;
+        builder.append_value(joined);
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Appends row `index` of a `List`/`LargeList`/`FixedSizeList` array to `out`,
+/// recursing into nested list elements.
+fn append_list_row(
+    array: &ArrayRef,
+    index: usize,
+    delimiter: &str,
+    null_string: Option<&str>,
+    out: &mut String,
+) -> Result<()> {
+    let child = match array.data_type() {
+        DataType::List(_) => array
+            .as_any()
+            .downcast_ref::<ListArray>()
+            .unwrap()
+            .value(index),
+        DataType::LargeList(_) => array
+            .as_any()
+            .downcast_ref::<LargeListArray>()
+            .unwrap()
+            .value(index),
+        DataType::FixedSizeList(_, _) => array
+            .as_any()
+            .downcast_ref::<FixedSizeListArray>()
+            .unwrap()
+            .value(index),
+        other => {
+            return exec_err!(
+                "array_to_string expects a List/LargeList/FixedSizeList, got {other}"
+            )
+        }
+    };
+
+    let is_nested_list = matches!(
+        child.data_type(),
+        DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _)
+    );
+
+    let mut first = true;
+    for i in 0..child.len() {
+        let rendered = if child.is_null(i) {
+            null_string.map(str::to_string)
+        } else if is_nested_list {
+            let mut nested = String::new();
+            append_list_row(&child, i, delimiter, null_string, &mut nested)?;
+            Some(nested)
+        } else {
+            Some(array_value_to_string(&child, i)?)
+        };
+
+        if let Some(rendered) = rendered {
+            if !first {
+                out.push_str(delimiter);
+            }
+            out.push_str(&rendered);
+            first = false;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads a single-row scalar `Utf8`/`LargeUtf8` argument (e.g. `delimiter` or
+/// `null_string`).
+fn get_scalar_utf8(array: &ArrayRef, what: &str) -> Result<String> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(array
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .value(0)
+            .to_string()),
+        DataType::LargeUtf8 => Ok(array
+            .as_any()
+            .downcast_ref::<LargeStringArray>()
+            .unwrap()
+            .value(0)
+            .to_string()),
+        other => exec_err!("array_to_string's {what} argument must be Utf8/LargeUtf8, got {other}"),
+    }
+}
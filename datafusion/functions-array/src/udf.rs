@@ -27,7 +27,7 @@ use datafusion_expr::expr::ScalarFunction;
 use datafusion_expr::type_coercion::binary::get_wider_type;
 use datafusion_expr::Expr;
 use datafusion_expr::TypeSignature;
-use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use datafusion_expr::{ColumnarValue, ScalarUDF, ScalarUDFImpl, Signature, Volatility};
 use std::any::Any;
 use std::cmp::Ordering;
 use std::sync::Arc;
@@ -37,12 +37,12 @@ use crate::utils::make_scalar_function;
 // Create static instances of ScalarUDFs for each function
 make_udf_function!(ArrayToString,
     array_to_string,
-    array delimiter, // arg name
+    array delimiter null_string, // arg name
     "converts each element to its text representation.", // doc
     array_to_string_udf // internal function name
 );
 #[derive(Debug)]
-pub(super) struct ArrayToString {
+pub struct ArrayToString {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -75,6 +75,21 @@ impl ScalarUDFImpl for ArrayToString {
 
     fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
         use DataType::*;
+        if arg_types.len() > 3 {
+            return plan_err!(
+                "The array_to_string function expects at most 3 arguments (array, delimiter, null_string)."
+            );
+        }
+        if let Some(null_string_type) = arg_types.get(2) {
+            match null_string_type {
+                Utf8 | LargeUtf8 => {}
+                _ => {
+                    return plan_err!(
+                        "The array_to_string function's null_string argument must be Utf8 or LargeUtf8."
+                    );
+                }
+            }
+        }
         Ok(match arg_types[0] {
             List(_) | LargeList(_) | FixedSizeList(_, _) => Utf8,
             _ => {
@@ -93,6 +108,50 @@ impl ScalarUDFImpl for ArrayToString {
     }
 }
 
+/// Validates the argument types of a `range`/`generate_series` call and returns the
+/// type to use as the resulting list's item type: `Int64` for the 1- and 2-arg forms
+/// and the `Int64` triple, `Date32` for the `Date32` triple, and the (shared)
+/// timestamp type for a `Timestamp` triple, whose `start` and `stop` must share the
+/// same unit and timezone and whose `step` must be a `MonthDayNano` interval.
+///
+/// `range`/`generate_series` accept any 3-arg shape at the signature level (there's
+/// no `Exact` wildcard for "any tz"), so this also covers the `Int64`/`Date32`
+/// triples and rejects anything else, rather than leaving non-`Timestamp` 3-arg
+/// combinations to fall through unchecked.
+fn validate_range_args(arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
+    use DataType::*;
+    match arg_types.len() {
+        1 => match &arg_types[0] {
+            Int64 => Ok(Int64),
+            other => plan_err!("range/generate_series argument must be Int64, got {other}"),
+        },
+        2 => match (&arg_types[0], &arg_types[1]) {
+            (Int64, Int64) => Ok(Int64),
+            (start, stop) => plan_err!(
+                "range/generate_series arguments must be (Int64, Int64), got ({start}, {stop})"
+            ),
+        },
+        3 => match (&arg_types[0], &arg_types[1], &arg_types[2]) {
+            (Int64, Int64, Int64) => Ok(Int64),
+            (Date32, Date32, Interval(MonthDayNano)) => Ok(Date32),
+            (
+                Timestamp(start_unit, start_tz),
+                Timestamp(stop_unit, stop_tz),
+                Interval(MonthDayNano),
+            ) if start_unit == stop_unit && start_tz == stop_tz => Ok(arg_types[0].clone()),
+            (Timestamp(_, _), Timestamp(_, _), Interval(MonthDayNano)) => {
+                plan_err!(
+                    "range/generate_series requires start and stop timestamps to share the same unit and timezone"
+                )
+            }
+            (start, stop, step) => plan_err!(
+                "range/generate_series arguments must be (Int64, Int64, Int64), (Date32, Date32, Interval(MonthDayNano)) or (Timestamp, Timestamp, Interval(MonthDayNano)), got ({start}, {stop}, {step})"
+            ),
+        },
+        other => plan_err!("range/generate_series takes 1 to 3 arguments, got {other}"),
+    }
+}
+
 make_udf_function!(
     Range,
     range,
@@ -101,7 +160,7 @@ make_udf_function!(
     range_udf
 );
 #[derive(Debug)]
-pub(super) struct Range {
+pub struct Range {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -115,6 +174,11 @@ impl Range {
                     TypeSignature::Exact(vec![Int64, Int64]),
                     TypeSignature::Exact(vec![Int64, Int64, Int64]),
                     TypeSignature::Exact(vec![Date32, Date32, Interval(MonthDayNano)]),
+                    // `Timestamp` carries an arbitrary unit/timezone that `Exact`
+                    // can't enumerate (there's no "any tz" wildcard), so accept any
+                    // 3-arg shape here and validate the concrete types (and that
+                    // both bounds share a unit/tz) in `return_type`.
+                    TypeSignature::Any(3),
                 ],
                 Volatility::Immutable,
             ),
@@ -135,12 +199,8 @@ impl ScalarUDFImpl for Range {
     }
 
     fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
-        use DataType::*;
-        Ok(List(Arc::new(Field::new(
-            "item",
-            arg_types[0].clone(),
-            true,
-        ))))
+        let item_type = validate_range_args(arg_types)?;
+        Ok(DataType::List(Arc::new(Field::new("item", item_type, true))))
     }
 
     fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
@@ -152,6 +212,9 @@ impl ScalarUDFImpl for Range {
             arrow::datatypes::DataType::Date32 => {
                 crate::kernels::gen_range_date(&args, false).map(ColumnarValue::Array)
             }
+            arrow::datatypes::DataType::Timestamp(_, _) => {
+                crate::kernels::gen_range_timestamp(&args, false).map(ColumnarValue::Array)
+            }
             _ => {
                 exec_err!("unsupported type for range")
             }
@@ -171,7 +234,7 @@ make_udf_function!(
     gen_series_udf
 );
 #[derive(Debug)]
-pub(super) struct GenSeries {
+pub struct GenSeries {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -185,6 +248,11 @@ impl GenSeries {
                     TypeSignature::Exact(vec![Int64, Int64]),
                     TypeSignature::Exact(vec![Int64, Int64, Int64]),
                     TypeSignature::Exact(vec![Date32, Date32, Interval(MonthDayNano)]),
+                    // `Timestamp` carries an arbitrary unit/timezone that `Exact`
+                    // can't enumerate (there's no "any tz" wildcard), so accept any
+                    // 3-arg shape here and validate the concrete types (and that
+                    // both bounds share a unit/tz) in `return_type`.
+                    TypeSignature::Any(3),
                 ],
                 Volatility::Immutable,
             ),
@@ -205,12 +273,8 @@ impl ScalarUDFImpl for GenSeries {
     }
 
     fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
-        use DataType::*;
-        Ok(List(Arc::new(Field::new(
-            "item",
-            arg_types[0].clone(),
-            true,
-        ))))
+        let item_type = validate_range_args(arg_types)?;
+        Ok(DataType::List(Arc::new(Field::new("item", item_type, true))))
     }
 
     fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
@@ -222,6 +286,9 @@ impl ScalarUDFImpl for GenSeries {
             arrow::datatypes::DataType::Date32 => {
                 crate::kernels::gen_range_date(&args, true).map(ColumnarValue::Array)
             }
+            arrow::datatypes::DataType::Timestamp(_, _) => {
+                crate::kernels::gen_range_timestamp(&args, true).map(ColumnarValue::Array)
+            }
             _ => {
                 exec_err!("unsupported type for range")
             }
@@ -242,7 +309,7 @@ make_udf_function!(
 );
 
 #[derive(Debug)]
-pub(super) struct ArrayDims {
+pub struct ArrayDims {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -308,7 +375,7 @@ impl Cardinality {
 }
 
 #[derive(Debug)]
-pub(super) struct Cardinality {
+pub struct Cardinality {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -353,7 +420,7 @@ make_udf_function!(
 );
 
 #[derive(Debug)]
-pub(super) struct ArrayNdims {
+pub struct ArrayNdims {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -398,6 +465,143 @@ impl ScalarUDFImpl for ArrayNdims {
     }
 }
 
+make_udf_function!(
+    MapKeys,
+    map_keys,
+    map,
+    "Return a list of all keys in the map.",
+    map_keys_udf
+);
+
+#[derive(Debug)]
+pub struct MapKeys {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl MapKeys {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+            aliases: vec![String::from("map_keys")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for MapKeys {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "map_keys"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
+        let (key_type, _) = get_map_entry_field_types(&arg_types[0], "map_keys")?;
+        Ok(DataType::List(Arc::new(Field::new("item", key_type, true))))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
+        make_scalar_function(crate::kernels::map_keys)(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+make_udf_function!(
+    MapValues,
+    map_values,
+    map,
+    "Return a list of all values in the map.",
+    map_values_udf
+);
+
+#[derive(Debug)]
+pub struct MapValues {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl MapValues {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+            aliases: vec![String::from("map_values")],
+        }
+    }
+}
+
+impl ScalarUDFImpl for MapValues {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "map_values"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
+        let (_, value_type) = get_map_entry_field_types(&arg_types[0], "map_values")?;
+        Ok(DataType::List(Arc::new(Field::new("item", value_type, true))))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
+        make_scalar_function(crate::kernels::map_values)(args)
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+/// Validates that `data_type` is a `Map` (physically a `List<Struct<keys, values>>`)
+/// and returns the data types of its key and value fields.
+fn get_map_entry_field_types(
+    data_type: &DataType,
+    function_name: &str,
+) -> datafusion_common::Result<(DataType, DataType)> {
+    match data_type {
+        DataType::Map(field, _) => match field.data_type() {
+            DataType::Struct(fields) if fields.len() == 2 => Ok((
+                fields[0].data_type().clone(),
+                fields[1].data_type().clone(),
+            )),
+            _ => plan_err!(
+                "The {function_name} function expects a Map whose entries are a struct with exactly 2 fields."
+            ),
+        },
+        _ => plan_err!("The {function_name} function can only accept a Map argument."),
+    }
+}
+
+/// Normalizes a `FixedSizeList` (and any `FixedSizeList`s nested within it) to the
+/// equivalent variable-length `List`, leaving other array types unchanged. Used by
+/// functions whose output no longer has a fixed size, e.g. appending/prepending an
+/// element or concatenating arrays.
+fn coerce_fixed_size_list_to_list(data_type: &DataType) -> DataType {
+    match data_type {
+        DataType::FixedSizeList(field, _) | DataType::List(field) => DataType::List(Arc::new(
+            Field::new(
+                field.name(),
+                coerce_fixed_size_list_to_list(field.data_type()),
+                field.is_nullable(),
+            ),
+        )),
+        _ => data_type.clone(),
+    }
+}
+
 make_udf_function!(
     ArrayAppend,
     array_append,
@@ -407,7 +611,7 @@ make_udf_function!(
 );
 
 #[derive(Debug)]
-pub(super) struct ArrayAppend {
+pub struct ArrayAppend {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -440,7 +644,7 @@ impl ScalarUDFImpl for ArrayAppend {
     }
 
     fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
-        Ok(arg_types[0].clone())
+        Ok(coerce_fixed_size_list_to_list(&arg_types[0]))
     }
 
     fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
@@ -461,7 +665,7 @@ make_udf_function!(
 );
 
 #[derive(Debug)]
-pub(super) struct ArrayPrepend {
+pub struct ArrayPrepend {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -494,7 +698,7 @@ impl ScalarUDFImpl for ArrayPrepend {
     }
 
     fn return_type(&self, arg_types: &[DataType]) -> datafusion_common::Result<DataType> {
-        Ok(arg_types[1].clone())
+        Ok(coerce_fixed_size_list_to_list(&arg_types[1]))
     }
 
     fn invoke(&self, args: &[ColumnarValue]) -> datafusion_common::Result<ColumnarValue> {
@@ -514,7 +718,7 @@ make_udf_function!(
 );
 
 #[derive(Debug)]
-pub(super) struct ArrayConcat {
+pub struct ArrayConcat {
     signature: Signature,
     aliases: Vec<String>,
 }
@@ -550,13 +754,14 @@ impl ScalarUDFImpl for ArrayConcat {
         let mut expr_type = DataType::Null;
         let mut max_dims = 0;
         for arg_type in arg_types {
-            match arg_type {
+            let arg_type = coerce_fixed_size_list_to_list(arg_type);
+            match &arg_type {
                 DataType::List(field) => {
                     if !field.data_type().equals_datatype(&DataType::Null) {
-                        let dims = list_ndims(arg_type);
+                        let dims = list_ndims(&arg_type);
                         expr_type = match max_dims.cmp(&dims) {
                             Ordering::Greater => expr_type,
-                            Ordering::Equal => get_wider_type(&expr_type, arg_type)?,
+                            Ordering::Equal => get_wider_type(&expr_type, &arg_type)?,
                             Ordering::Less => {
                                 max_dims = dims;
                                 arg_type.clone()
@@ -653,3 +858,23 @@ impl ScalarUDFImpl for MakeArray {
         &self.aliases
     }
 }
+
+/// Returns all array functions as a `Vec<Arc<ScalarUDF>>` so downstream crates can
+/// register a subset of them into their own `FunctionRegistry` instead of pulling in
+/// the entire default set.
+pub fn all_default_array_functions() -> Vec<Arc<ScalarUDF>> {
+    vec![
+        array_to_string_udf(),
+        range_udf(),
+        gen_series_udf(),
+        array_dims_udf(),
+        cardinality_udf(),
+        array_ndims_udf(),
+        map_keys_udf(),
+        map_values_udf(),
+        array_append_udf(),
+        array_prepend_udf(),
+        array_concat_udf(),
+        make_array_udf(),
+    ]
+}